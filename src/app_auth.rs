@@ -0,0 +1,81 @@
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use serde::Serialize;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Serialize)]
+struct Claims {
+    iat: u64,
+    exp: u64,
+    iss: String,
+}
+
+/// Mints and caches short-lived GitHub App installation tokens so long-running
+/// orchestrators don't fail once a static token expires. Call `token()` before each
+/// network operation; it only mints a new token when the cached one is missing or close
+/// to expiring.
+pub struct AppAuth {
+    app_id: String,
+    private_key_pem: String,
+    installation_id: String,
+    cached: Option<(String, SystemTime)>,
+}
+
+impl AppAuth {
+    pub fn new(app_id: String, private_key_pem: String, installation_id: String) -> Self {
+        Self {
+            app_id,
+            private_key_pem,
+            installation_id,
+            cached: None,
+        }
+    }
+
+    pub fn token(&mut self) -> Result<String, Box<dyn std::error::Error>> {
+        if let Some((token, expires_at)) = &self.cached {
+            if *expires_at > SystemTime::now() + Duration::from_secs(60) {
+                return Ok(token.clone());
+            }
+        }
+
+        let (token, expires_at) = self.mint()?;
+        self.cached = Some((token.clone(), expires_at));
+        Ok(token)
+    }
+
+    fn mint(&self) -> Result<(String, SystemTime), Box<dyn std::error::Error>> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let claims = Claims {
+            iat: now - 60,
+            exp: now + 9 * 60,
+            iss: self.app_id.clone(),
+        };
+        let key = EncodingKey::from_rsa_pem(self.private_key_pem.as_bytes())?;
+        let jwt = encode(&Header::new(Algorithm::RS256), &claims, &key)?;
+
+        let url = format!(
+            "https://api.github.com/app/installations/{}/access_tokens",
+            self.installation_id
+        );
+        let client = reqwest::blocking::Client::new();
+        let resp = client
+            .post(&url)
+            .bearer_auth(jwt)
+            .header("User-Agent", "mittorch")
+            .header("Accept", "application/vnd.github+json")
+            .send()?;
+
+        if !resp.status().is_success() {
+            return Err(format!("failed to mint installation token: {}", resp.status()).into());
+        }
+
+        let json: serde_json::Value = resp.json()?;
+        let token = json["token"]
+            .as_str()
+            .ok_or("installation token response missing \"token\"")?
+            .to_string();
+
+        // GitHub installation tokens are valid for one hour; refresh a few minutes early.
+        let expires_at = SystemTime::now() + Duration::from_secs(55 * 60);
+        Ok((token, expires_at))
+    }
+}
@@ -1,11 +1,15 @@
 use serde::Deserialize;
 use colored::*;
+use std::collections::HashSet;
 use std::fs;
 use std::io::ErrorKind;
 use std::error::Error;
 
-#[derive(Debug, Deserialize)]
-pub struct Config {
+use crate::notifier::NotifierSink;
+
+/// A single repository/service entry supervised independently of the others.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ServiceConfig {
     pub account: String,
     pub repository: String,
     pub branch: String,
@@ -19,18 +23,144 @@ pub struct Config {
 
     #[serde(rename = "stop-command", default)]
     pub stop_command: Option<String>,
+
+    #[serde(rename = "webhook-addr", default = "default_webhook_addr")]
+    pub webhook_addr: String,
+
+    #[serde(rename = "webhook-port", default = "default_webhook_port")]
+    pub webhook_port: u16,
+
+    #[serde(rename = "webhook-secret", default)]
+    pub webhook_secret: Option<String>,
+
+    #[serde(rename = "data-dir", default)]
+    pub data_dir: Option<String>,
+
+    /// `"app"` to authenticate as a GitHub App installation instead of a classic/fine-grained
+    /// PAT. With `app-id`/`app-private-key`/`app-installation-id` also set, installation
+    /// tokens are minted and refreshed automatically; otherwise `token` is treated as a
+    /// caller-supplied installation token.
+    #[serde(default)]
+    pub auth: Option<String>,
+
+    #[serde(rename = "app-id", default)]
+    pub app_id: Option<String>,
+
+    #[serde(rename = "app-private-key", default)]
+    pub app_private_key: Option<String>,
+
+    #[serde(rename = "app-installation-id", default)]
+    pub app_installation_id: Option<String>,
+
+    /// Sliding window (seconds) used to detect a crash loop.
+    #[serde(rename = "restart-window-secs", default = "default_restart_window_secs")]
+    pub restart_window_secs: u64,
+
+    /// Once more than this many restarts land inside `restart-window-secs`, exponential
+    /// backoff is applied before the next restart attempt.
+    #[serde(rename = "restart-max-in-window", default = "default_restart_max_in_window")]
+    pub restart_max_in_window: u32,
+
+    #[serde(rename = "restart-backoff-base-secs", default = "default_restart_backoff_base_secs")]
+    pub restart_backoff_base_secs: u64,
+
+    #[serde(rename = "restart-backoff-cap-secs", default = "default_restart_backoff_cap_secs")]
+    pub restart_backoff_cap_secs: u64,
+
+    /// After this many consecutive crash-triggered restarts, stop auto-restarting and hold
+    /// until the next detected upstream commit (assumed to be a potential fix).
+    #[serde(rename = "restart-max-failures", default = "default_restart_max_failures")]
+    pub restart_max_failures: u32,
+}
+
+impl ServiceConfig {
+    /// The directory the repository is checked out into — `data-dir` if configured,
+    /// otherwise `.data/<account>/<repository>`. Namespacing by account keeps two services
+    /// that happen to share a repository short name (different accounts, or forks) from
+    /// checking out into — and stomping on — the same directory.
+    pub fn repo_dir(&self) -> String {
+        self.data_dir
+            .clone()
+            .unwrap_or_else(|| format!(".data/{}/{}", self.account, self.repository))
+    }
+
+    pub fn uses_app_auth(&self) -> bool {
+        self.auth.as_deref() == Some("app")
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Config {
+    pub services: Vec<ServiceConfig>,
+
+    #[serde(rename = "db-path", default = "default_db_path")]
+    pub db_path: String,
+
+    #[serde(default)]
+    pub notifiers: Vec<NotifierSink>,
 }
 
 fn default_interval() -> u64 {
     60
 }
 
+fn default_webhook_addr() -> String {
+    "0.0.0.0".to_string()
+}
+
+fn default_webhook_port() -> u16 {
+    8787
+}
+
+fn default_db_path() -> String {
+    "mittorch.db".to_string()
+}
+
+fn default_restart_window_secs() -> u64 {
+    60
+}
+
+fn default_restart_max_in_window() -> u32 {
+    5
+}
+
+fn default_restart_backoff_base_secs() -> u64 {
+    1
+}
+
+fn default_restart_backoff_cap_secs() -> u64 {
+    60
+}
+
+fn default_restart_max_failures() -> u32 {
+    10
+}
+
 impl Config {
     pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
         match fs::read_to_string(path) {
             Ok(data) => {
                 let config: Config = serde_json::from_str(&data)?;
-                println!("{} Loaded config from {}", "SUCCESS:".green().bold(), path);
+
+                let mut seen_dirs = HashSet::new();
+                for service in &config.services {
+                    let dir = service.repo_dir();
+                    if !seen_dirs.insert(dir.clone()) {
+                        eprintln!(
+                            "{} Two services both resolve to repo-dir \"{}\" — set distinct \"data-dir\" values to avoid one checkout stomping on the other.",
+                            "FAILURE:".red().bold(),
+                            dir
+                        );
+                        std::process::exit(1);
+                    }
+                }
+
+                println!(
+                    "{} Loaded config from {} ({} service(s))",
+                    "SUCCESS:".green().bold(),
+                    path,
+                    config.services.len()
+                );
                 Ok(config)
             }
             Err(err) if err.kind() == ErrorKind::NotFound => {
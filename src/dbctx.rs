@@ -0,0 +1,83 @@
+use rusqlite::{params, Connection};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A local SQLite-backed record of the orchestrator's activity: detected commits,
+/// process start/stop/restart events, and update attempts. Gives operators an
+/// auditable timeline of deploys and crash loops that survives restarts.
+pub struct DbCtx {
+    conn: Connection,
+}
+
+impl DbCtx {
+    pub fn open(path: &str) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+        // Each supervised service opens its own connection to this same file. Under WAL,
+        // readers and writers don't block each other, and busy_timeout makes the rare
+        // remaining writer-writer conflict retry instead of failing the insert outright.
+        conn.execute_batch(
+            "PRAGMA journal_mode=WAL;
+            PRAGMA busy_timeout=5000;
+            CREATE TABLE IF NOT EXISTS commits (
+                id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                local_sha   TEXT NOT NULL,
+                remote_sha  TEXT NOT NULL,
+                branch      TEXT NOT NULL,
+                detected_at INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS process_events (
+                id        INTEGER PRIMARY KEY AUTOINCREMENT,
+                pid       INTEGER NOT NULL,
+                exit_code INTEGER,
+                reason    TEXT NOT NULL,
+                at        INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS update_attempts (
+                id      INTEGER PRIMARY KEY AUTOINCREMENT,
+                old_sha TEXT NOT NULL,
+                new_sha TEXT NOT NULL,
+                success INTEGER NOT NULL,
+                error   TEXT,
+                at      INTEGER NOT NULL
+            );",
+        )?;
+        Ok(Self { conn })
+    }
+
+    pub fn record_commit(&self, local_sha: &str, remote_sha: &str, branch: &str) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "INSERT INTO commits (local_sha, remote_sha, branch, detected_at) VALUES (?1, ?2, ?3, ?4)",
+            params![local_sha, remote_sha, branch, now()],
+        )?;
+        Ok(())
+    }
+
+    /// `reason` is one of `"start"`, `"crash"`, `"update"`, or `"signal"`.
+    pub fn record_process_event(&self, pid: u32, exit_code: Option<i32>, reason: &str) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "INSERT INTO process_events (pid, exit_code, reason, at) VALUES (?1, ?2, ?3, ?4)",
+            params![pid, exit_code, reason, now()],
+        )?;
+        Ok(())
+    }
+
+    pub fn record_update_attempt(
+        &self,
+        old_sha: &str,
+        new_sha: &str,
+        success: bool,
+        error: Option<&str>,
+    ) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "INSERT INTO update_attempts (old_sha, new_sha, success, error, at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![old_sha, new_sha, success as i64, error, now()],
+        )?;
+        Ok(())
+    }
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
@@ -3,23 +3,42 @@ use std::path::Path;
 use colored::*;
 use git2::Repository;
 
+use crate::redact::redact;
+
+/// How credentials are presented to git and the GitHub API. Classic/fine-grained PATs use
+/// `AuthMode::Pat`; GitHub App installation tokens (static or minted via `app_auth`) use
+/// `AuthMode::App`, which changes the clone URL form and the API `Authorization` scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthMode {
+    Pat,
+    App,
+}
+
+fn build_repo_url(account: &str, repository: &str, token: Option<&str>, auth_mode: AuthMode) -> String {
+    match (token.map(|t| t.trim()).filter(|t| !t.is_empty()), auth_mode) {
+        (Some(tok), AuthMode::App) => {
+            format!("https://x-access-token:{}@github.com/{}/{}.git", tok, account, repository)
+        }
+        (Some(tok), AuthMode::Pat) => format!("https://{}@github.com/{}/{}.git", tok, account, repository),
+        (None, _) => format!("https://github.com/{}/{}.git", account, repository),
+    }
+}
+
 pub fn prepare_repository(
+    repo_path: &Path,
     account: &str,
     repository: &str,
     branch: &str,
     token: Option<&str>,
+    auth_mode: AuthMode,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let target_dir = Path::new(".data");
-    if !target_dir.exists() {
-        fs::create_dir_all(target_dir)?;
+    if let Some(parent) = repo_path.parent() {
+        if !parent.as_os_str().is_empty() && !parent.exists() {
+            fs::create_dir_all(parent)?;
+        }
     }
 
-    let repo_path = target_dir.join(repository);
-
-    let repo_url = match token.map(|t| t.trim()).filter(|t| !t.is_empty()) {
-        Some(tok) => format!("https://{}@github.com/{}/{}.git", tok, account, repository),
-        None => format!("https://github.com/{}/{}.git", account, repository),
-    };
+    let repo_url = build_repo_url(account, repository, token, auth_mode);
 
     if repo_path.exists() {
         println!(
@@ -27,12 +46,18 @@ pub fn prepare_repository(
             "WARNING:".yellow().bold(),
             repo_path.display()
         );
-        fs::remove_dir_all(&repo_path)?;
+        fs::remove_dir_all(repo_path)?;
     }
 
-    println!("{} Cloning {} (branch: {})", "UPDATED:".bright_black().bold(), repo_url, branch);
+    let secrets: &[&str] = &[token.map(|t| t.trim()).unwrap_or("")];
+    println!(
+        "{} Cloning {} (branch: {})",
+        "UPDATED:".bright_black().bold(),
+        redact(&repo_url, secrets),
+        branch
+    );
 
-    match Repository::clone(&repo_url, &repo_path) {
+    match Repository::clone(&repo_url, repo_path) {
         Ok(repo) => {
             let _ = repo.set_head(&format!("refs/heads/{}", branch));
             println!("{} Repository ready.", "SUCCESS:".green().bold());
@@ -42,13 +67,13 @@ pub fn prepare_repository(
                 eprintln!(
                     "{} Failed to clone private repository — check token permissions: {}",
                     "FAILURE:".red().bold(),
-                    e
+                    redact(&e.to_string(), secrets)
                 );
             } else {
                 eprintln!(
                     "{} Failed to clone public repository: {}",
                     "FAILURE:".red().bold(),
-                    e
+                    redact(&e.to_string(), secrets)
                 );
             }
             return Err(Box::new(e));
@@ -63,11 +88,90 @@ pub fn get_local_commit_hash(repo: &Repository) -> Result<String, git2::Error> {
     Ok(head.id().to_string())
 }
 
+/// Fetches `branch` from `origin` and hard-resets the working tree to its tip, avoiding the
+/// cost of wiping and re-cloning the repository on every update. Falls back to a fresh
+/// `prepare_repository` clone when the local repository is missing or otherwise unopenable.
+pub fn fetch_and_reset(
+    repo_path: &Path,
+    account: &str,
+    repository: &str,
+    branch: &str,
+    token: Option<&str>,
+    auth_mode: AuthMode,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let repo = match Repository::open(repo_path) {
+        Ok(repo) => repo,
+        Err(_) => {
+            println!(
+                "{} No local repository to fetch — falling back to a fresh clone.",
+                "WARNING:".yellow().bold()
+            );
+            return prepare_repository(repo_path, account, repository, branch, token, auth_mode);
+        }
+    };
+
+    // Installation tokens expire in about an hour, so refresh the remote URL with the
+    // current token before every fetch rather than relying on the one baked in at clone time.
+    let repo_url = build_repo_url(account, repository, token, auth_mode);
+    repo.remote_set_url("origin", &repo_url)?;
+
+    let mut remote = repo.find_remote("origin")?;
+    println!("{} Fetching {} (branch: {})", "UPDATED:".bright_black().bold(), repository, branch);
+
+    // A fetch failure (network blip, expired token, rate limit) is transient — leave the
+    // working tree untouched and let the next tick retry. Only a missing/corrupt local repo
+    // (caught above and below, via `Repository::open`/ref resolution) warrants a re-clone.
+    remote.fetch(&[branch], None, None).inspect_err(|err| {
+        eprintln!(
+            "{} Fetch failed: {}",
+            "FAILURE:".red().bold(),
+            redact(&err.to_string(), &[token.map(|t| t.trim()).unwrap_or("")])
+        );
+    })?;
+
+    // Resolve down to an owned `Oid` rather than matching on the borrowing `Commit`/`Result`
+    // directly — holding that borrow alive into the error arm would conflict with the
+    // `drop(repo)` below.
+    let remote_ref = format!("refs/remotes/origin/{}", branch);
+    let target_oid = match repo
+        .find_reference(&remote_ref)
+        .and_then(|r| r.peel_to_commit())
+        .map(|commit| commit.id())
+    {
+        Ok(oid) => oid,
+        Err(err) => {
+            eprintln!(
+                "{} Local repository appears corrupt ({}) — falling back to a fresh clone.",
+                "WARNING:".yellow().bold(),
+                err
+            );
+            drop(remote);
+            drop(repo);
+            fs::remove_dir_all(repo_path)?;
+            return prepare_repository(repo_path, account, repository, branch, token, auth_mode);
+        }
+    };
+
+    let target = repo.find_commit(target_oid)?;
+    repo.reset(target.as_object(), git2::ResetType::Hard, None)?;
+    let _ = repo.set_head(&format!("refs/heads/{}", branch));
+
+    let target_sha = target.id().to_string();
+    println!(
+        "{} Repository reset to {}.",
+        "SUCCESS:".green().bold(),
+        &target_sha[..8.min(target_sha.len())]
+    );
+
+    Ok(())
+}
+
 pub fn get_latest_remote_sha(
     account: &str,
     repository: &str,
     branch: &str,
     token: Option<&str>,
+    auth_mode: AuthMode,
 ) -> Result<String, Box<dyn std::error::Error>> {
     let url = format!(
         "https://api.github.com/repos/{}/{}/branches/{}",
@@ -78,7 +182,11 @@ pub fn get_latest_remote_sha(
     let mut req = client.get(&url).header("User-Agent", "mittorch");
 
     if let Some(tok) = token.map(|t| t.trim()).filter(|t| !t.is_empty()) {
-        req = req.header("Authorization", format!("token {}", tok));
+        let scheme = match auth_mode {
+            AuthMode::App => "Bearer",
+            AuthMode::Pat => "token",
+        };
+        req = req.header("Authorization", format!("{} {}", scheme, tok));
     }
 
     let resp = req.send()?;
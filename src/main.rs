@@ -1,91 +1,227 @@
 use colored::*;
 use std::error::Error;
-use std::fs;
 use std::path::Path;
 use std::process::{Command, Child};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::{thread, time::Duration};
+use std::{thread, time::Duration, time::Instant};
 
+mod app_auth;
 mod config;
+mod dbctx;
 mod github;
+mod notifier;
+mod redact;
+mod webhook;
 
-use config::Config;
-use github::{prepare_repository, get_latest_remote_sha, get_local_commit_hash};
+use app_auth::AppAuth;
+use config::{Config, ServiceConfig};
+use dbctx::DbCtx;
+use github::{prepare_repository, fetch_and_reset, get_latest_remote_sha, get_local_commit_hash, AuthMode};
+use notifier::Notifier;
+use webhook::PushEvent;
 
 fn main() -> Result<(), Box<dyn Error>> {
     let config = Config::load("mittorch.json")?;
-    let repo_dir = format!(".data/{}", config.repository);
+
+    println!(
+        "{} Starting mittorch orchestrator ({} service(s))",
+        "UPDATED:".bright_black().bold(),
+        config.services.len()
+    );
+
+    let running = Arc::new(AtomicBool::new(true));
+    let r = running.clone();
+    ctrlc::set_handler(move || {
+        println!("\n{} Signal received, stopping all services...", "WARNING:".yellow().bold());
+        r.store(false, Ordering::SeqCst);
+    })?;
+
+    let notifier = Arc::new(Notifier::new(config.notifiers.clone()));
+
+    // Each service gets its own thread and update loop so a crash or stalled reload in one
+    // service never blocks update checks for the others.
+    let handles: Vec<_> = config
+        .services
+        .into_iter()
+        .map(|service| {
+            let db_path = config.db_path.clone();
+            let notifier = notifier.clone();
+            let running = running.clone();
+            thread::spawn(move || {
+                let name = format!("{}/{}", service.account, service.repository);
+                if let Err(err) = supervise_service(service, &db_path, notifier, running) {
+                    eprintln!(
+                        "{} Service {} stopped with error: {}",
+                        "FAILURE:".red().bold(),
+                        name,
+                        err
+                    );
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        let _ = handle.join();
+    }
+
+    println!("{} Mittorch exited cleanly.", "SUCCESS:".green().bold());
+    Ok(())
+}
+
+/// Supervises a single service end-to-end: initial clone, process lifecycle, and the
+/// update loop (webhook-driven or polling). Runs until `running` is cleared.
+fn supervise_service(
+    service: ServiceConfig,
+    db_path: &str,
+    notifier: Arc<Notifier>,
+    running: Arc<AtomicBool>,
+) -> Result<(), Box<dyn Error>> {
+    let repo_dir = service.repo_dir();
     let repo_path = Path::new(&repo_dir);
+    let db = DbCtx::open(db_path)?;
 
-    println!("{} Starting mittorch orchestrator", "UPDATED:".bright_black().bold());
+    let mut app_auth = match (
+        service.uses_app_auth(),
+        &service.app_id,
+        &service.app_private_key,
+        &service.app_installation_id,
+    ) {
+        (true, Some(app_id), Some(key), Some(installation_id)) => Some(AppAuth::new(
+            app_id.clone(),
+            key.clone(),
+            installation_id.clone(),
+        )),
+        _ => None,
+    };
 
+    let (token, auth_mode) = resolve_credentials(&service, &mut app_auth)?;
     if let Err(err) = prepare_repository(
-        &config.account,
-        &config.repository,
-        &config.branch,
-        config.token.as_deref(),
+        repo_path,
+        &service.account,
+        &service.repository,
+        &service.branch,
+        token.as_deref(),
+        auth_mode,
     ) {
         eprintln!("{} Initial clone failed: {}", "FAILURE:".red().bold(), err);
     } else {
         println!("{} Repository prepared.", "SUCCESS:".green().bold());
     }
 
-    let mut child = if let Some(cmd) = &config.start_command {
+    let mut child = if let Some(cmd) = &service.start_command {
         start_process(cmd, repo_path)?
     } else {
         return Err("No start-command configured.".into());
     };
+    log_db_err(db.record_process_event(child.id(), None, "start"));
+    notifier.notify("process_started", &[("pid", &child.id().to_string())]);
 
-    let running = Arc::new(AtomicBool::new(true));
-    let r = running.clone();
-    ctrlc::set_handler(move || {
-        println!("\n{} Signal received, stopping...", "WARNING:".yellow().bold());
-        r.store(false, Ordering::SeqCst);
-    })?;
+    // Crash-loop tracking: restart timestamps within the sliding window, consecutive
+    // crash-triggered restarts, and whether the service is currently held (auto-restart
+    // paused until the next detected upstream commit).
+    let mut restart_times: Vec<Instant> = Vec::new();
+    let mut consecutive_failures: u32 = 0;
+    let mut held = false;
+
+    // When a webhook secret is configured, reload on verified pushes instead of polling.
+    // Polling remains as a fallback for services with no webhook configured.
+    let push_events = match &service.webhook_secret {
+        Some(secret) if !secret.trim().is_empty() => {
+            match webhook::listen(
+                &service.webhook_addr,
+                service.webhook_port,
+                secret.clone(),
+                running.clone(),
+            ) {
+                Ok(rx) => Some(rx),
+                Err(err) => {
+                    // `child` is already running at this point — with nothing left to
+                    // supervise it, it must be torn down before we bail out.
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(err.into());
+                }
+            }
+        }
+        _ => None,
+    };
 
     while running.load(Ordering::SeqCst) {
-        thread::sleep(Duration::from_secs(config.interval));
+        let latest_push = match &push_events {
+            Some(rx) => {
+                thread::sleep(Duration::from_secs(1));
+                let mut latest: Option<PushEvent> = None;
+                while let Ok(event) = rx.try_recv() {
+                    latest = Some(event);
+                }
+                latest
+            }
+            None => {
+                thread::sleep(Duration::from_secs(service.interval));
+                None
+            }
+        };
+
+        let (token, auth_mode) = resolve_credentials(&service, &mut app_auth)?;
 
         // --- Crash or exit handling ---
         if let Some(status) = child.try_wait()? {
-            eprintln!(
-                "{} Supervised process exited with code {:?}",
-                "WARNING:".yellow().bold(),
-                status.code()
-            );
+            // While held, the same already-reaped child keeps reporting this exit on every
+            // tick — log and notify only once, when the crash is first observed.
+            if !held {
+                eprintln!(
+                    "{} Supervised process exited with code {:?}",
+                    "WARNING:".yellow().bold(),
+                    status.code()
+                );
+                log_db_err(db.record_process_event(child.id(), status.code(), "crash"));
+                notifier.notify(
+                    "process_crashed",
+                    &[("exit_code", &status.code().map(|c| c.to_string()).unwrap_or_else(|| "unknown".to_string()))],
+                );
+            }
 
             // Before restarting, check for possible repo update
             println!("{} Checking for possible updates before restart...", "UPDATED:".bright_black().bold());
-            match git2::Repository::open(&repo_path) {
+            let mut update_applied = false;
+            match git2::Repository::open(repo_path) {
                 Ok(repo) => {
                     let local_sha = get_local_commit_hash(&repo).unwrap_or_default();
-                    match get_latest_remote_sha(
-                        &config.account,
-                        &config.repository,
-                        &config.branch,
-                        config.token.as_deref(),
-                    ) {
-                        Ok(remote_sha) => {
+                    match resolve_remote_sha(&service, latest_push.as_ref(), token.as_deref(), auth_mode) {
+                        Ok(None) => {
+                            println!("{} No new commits detected.", "UPDATED:".bright_black().bold());
+                        }
+                        Ok(Some(remote_sha)) => {
                             if !local_sha.is_empty() && !remote_sha.is_empty() && local_sha != remote_sha {
-                                println!("{} Update available: {} → {}", 
+                                println!("{} Update available: {} → {}",
                                     "UPDATED:".bright_black().bold(),
-                                    short_sha(&local_sha), 
+                                    short_sha(&local_sha),
                                     short_sha(&remote_sha)
                                 );
+                                log_db_err(db.record_commit(&local_sha, &remote_sha, &service.branch));
 
                                 println!("{} Updating repository before restart...", "WARNING:".yellow().bold());
-                                if let Err(err) = fs::remove_dir_all(&repo_path) {
-                                    eprintln!("{} Cleanup failed: {}", "FAILURE:".red().bold(), err);
-                                } else if let Err(err) = prepare_repository(
-                                    &config.account,
-                                    &config.repository,
-                                    &config.branch,
-                                    config.token.as_deref(),
+                                if let Err(err) = fetch_and_reset(
+                                    repo_path,
+                                    &service.account,
+                                    &service.repository,
+                                    &service.branch,
+                                    token.as_deref(),
+                                    auth_mode,
                                 ) {
-                                    eprintln!("{} Re-clone failed: {}", "FAILURE:".red().bold(), err);
+                                    eprintln!("{} Update failed: {}", "FAILURE:".red().bold(), err);
+                                    log_db_err(db.record_update_attempt(&local_sha, &remote_sha, false, Some(&err.to_string())));
+                                    notifier.notify("reload_failure", &[("error", &err.to_string())]);
                                 } else {
                                     println!("{} Repository updated successfully.", "SUCCESS:".green().bold());
+                                    log_db_err(db.record_update_attempt(&local_sha, &remote_sha, true, None));
+                                    notifier.notify(
+                                        "update_applied",
+                                        &[("old_sha", &local_sha), ("new_sha", &remote_sha)],
+                                    );
+                                    update_applied = true;
                                 }
                             } else {
                                 println!("{} No new commits detected.", "UPDATED:".bright_black().bold());
@@ -99,10 +235,57 @@ fn main() -> Result<(), Box<dyn Error>> {
                 }
             }
 
-            // Restart process regardless of update
-            if let Some(start) = &config.start_command {
+            // An update is assumed to be a potential fix, so it clears a held state and
+            // resets the crash-loop counters.
+            if update_applied {
+                held = false;
+                consecutive_failures = 0;
+                restart_times.clear();
+            }
+
+            if held {
+                eprintln!(
+                    "{} Service is held after {} consecutive crashes — waiting for an upstream update.",
+                    "WARNING:".yellow().bold(),
+                    consecutive_failures
+                );
+                continue;
+            }
+
+            let now = Instant::now();
+            let window = Duration::from_secs(service.restart_window_secs);
+            restart_times.retain(|t| now.duration_since(*t) <= window);
+            restart_times.push(now);
+
+            if restart_times.len() as u32 > service.restart_max_in_window {
+                let backoff_secs = service
+                    .restart_backoff_base_secs
+                    .saturating_mul(1u64 << consecutive_failures.min(32))
+                    .min(service.restart_backoff_cap_secs);
+                println!(
+                    "{} Crash loop detected ({} restarts in {}s) — backing off {}s before restart.",
+                    "WARNING:".yellow().bold(),
+                    restart_times.len(),
+                    service.restart_window_secs,
+                    backoff_secs
+                );
+                interruptible_sleep(Duration::from_secs(backoff_secs), &running);
+            }
+
+            if let Some(start) = &service.start_command {
                 child = start_process(start, repo_path)?;
+                log_db_err(db.record_process_event(child.id(), None, "crash"));
                 println!("{} Process restarted after crash.", "SUCCESS:".green().bold());
+
+                consecutive_failures += 1;
+                if consecutive_failures >= service.restart_max_failures {
+                    held = true;
+                    eprintln!(
+                        "{} Service held after {} consecutive crash-triggered restarts.",
+                        "WARNING:".yellow().bold(),
+                        consecutive_failures
+                    );
+                }
             } else {
                 eprintln!("{} No start command configured — cannot restart.", "FAILURE:".red().bold());
             }
@@ -110,16 +293,33 @@ fn main() -> Result<(), Box<dyn Error>> {
             continue;
         }
 
+        // The process survived this tick without crashing — the consecutive-failure count
+        // starts over. Earlier restarts stay in `restart_times` and age out of the sliding
+        // window on their own, so a service that keeps crashing a few seconds after each
+        // start still trips the crash-loop backoff.
+        if consecutive_failures > 0 {
+            consecutive_failures = 0;
+        }
+
         // --- Regular update check loop ---
-        let repo = match git2::Repository::open(&repo_path) {
+        // In webhook mode, only a verified push warrants opening the repo and querying the
+        // remote SHA — skip the check entirely on ticks with nothing new, so idle services
+        // don't spam "No changes detected." once a second and re-open the repo for no reason.
+        if push_events.is_some() && latest_push.is_none() {
+            continue;
+        }
+
+        let repo = match git2::Repository::open(repo_path) {
             Ok(r) => r,
             Err(_) => {
                 eprintln!("{} Local repo missing — retrying clone.", "WARNING:".yellow().bold());
                 if let Err(err) = prepare_repository(
-                    &config.account,
-                    &config.repository,
-                    &config.branch,
-                    config.token.as_deref(),
+                    repo_path,
+                    &service.account,
+                    &service.repository,
+                    &service.branch,
+                    token.as_deref(),
+                    auth_mode,
                 ) {
                     eprintln!("{} Retry failed: {}", "FAILURE:".red().bold(), err);
                 } else {
@@ -130,13 +330,12 @@ fn main() -> Result<(), Box<dyn Error>> {
         };
 
         let local_sha = get_local_commit_hash(&repo).unwrap_or_default();
-        let remote_sha = match get_latest_remote_sha(
-            &config.account,
-            &config.repository,
-            &config.branch,
-            config.token.as_deref(),
-        ) {
-            Ok(sha) => sha,
+        let remote_sha = match resolve_remote_sha(&service, latest_push.as_ref(), token.as_deref(), auth_mode) {
+            Ok(Some(sha)) => sha,
+            Ok(None) => {
+                println!("{} No changes detected.", "UPDATED:".bright_black().bold());
+                continue;
+            }
             Err(err) => {
                 eprintln!("{} Failed to query remote SHA: {}", "FAILURE:".red().bold(), err);
                 continue;
@@ -155,8 +354,9 @@ fn main() -> Result<(), Box<dyn Error>> {
                 short_sha(&local_sha),
                 short_sha(&remote_sha)
             );
+            log_db_err(db.record_commit(&local_sha, &remote_sha, &service.branch));
 
-            if let Some(stop) = &config.stop_command {
+            if let Some(stop) = &service.stop_command {
                 run_command("stop", stop, repo_path)?;
                 thread::sleep(Duration::from_secs(1));
             } else {
@@ -165,24 +365,25 @@ fn main() -> Result<(), Box<dyn Error>> {
                 let _ = child.wait();
             }
 
-            println!("{} Removing old repository...", "WARNING:".yellow().bold());
-            if let Err(err) = fs::remove_dir_all(&repo_path) {
-                eprintln!("{} Cleanup failed: {}", "FAILURE:".red().bold(), err);
-                continue;
-            }
-
-            if let Err(err) = prepare_repository(
-                &config.account,
-                &config.repository,
-                &config.branch,
-                config.token.as_deref(),
+            if let Err(err) = fetch_and_reset(
+                repo_path,
+                &service.account,
+                &service.repository,
+                &service.branch,
+                token.as_deref(),
+                auth_mode,
             ) {
-                eprintln!("{} Re-clone failed: {}", "FAILURE:".red().bold(), err);
+                eprintln!("{} Update failed: {}", "FAILURE:".red().bold(), err);
+                log_db_err(db.record_update_attempt(&local_sha, &remote_sha, false, Some(&err.to_string())));
+                notifier.notify("reload_failure", &[("error", &err.to_string())]);
                 continue;
             }
+            log_db_err(db.record_update_attempt(&local_sha, &remote_sha, true, None));
+            notifier.notify("update_applied", &[("old_sha", &local_sha), ("new_sha", &remote_sha)]);
 
-            if let Some(start) = &config.start_command {
+            if let Some(start) = &service.start_command {
                 child = start_process(start, repo_path)?;
+                log_db_err(db.record_process_event(child.id(), None, "update"));
             }
 
             println!("{} Reloaded cleanly.", "SUCCESS:".green().bold());
@@ -192,13 +393,71 @@ fn main() -> Result<(), Box<dyn Error>> {
     }
 
     println!("{} Stopping supervised process...", "WARNING:".yellow().bold());
+    log_db_err(db.record_process_event(child.id(), None, "signal"));
+    notifier.notify("process_stopped", &[("pid", &child.id().to_string())]);
     let _ = child.kill();
     let _ = child.wait();
 
-    println!("{} Mittorch exited cleanly.", "SUCCESS:".green().bold());
     Ok(())
 }
 
+/// Determines the remote SHA to compare against local HEAD. In webhook mode, only a
+/// verified push matching the configured branch counts as an update (`Ok(None)` otherwise);
+/// in polling mode (no webhook secret configured) the GitHub API is queried directly.
+fn resolve_remote_sha(
+    service: &ServiceConfig,
+    push: Option<&PushEvent>,
+    token: Option<&str>,
+    auth_mode: AuthMode,
+) -> Result<Option<String>, Box<dyn Error>> {
+    if service.webhook_secret.as_deref().unwrap_or("").trim().is_empty() {
+        return get_latest_remote_sha(&service.account, &service.repository, &service.branch, token, auth_mode)
+            .map(Some);
+    }
+
+    let expected_ref = format!("refs/heads/{}", service.branch);
+    let expected_repo = format!("{}/{}", service.account, service.repository);
+    Ok(push
+        .filter(|event| event.ref_name == expected_ref && event.full_name == expected_repo)
+        .map(|event| event.after.clone()))
+}
+
+/// Resolves the token and auth scheme to use for the next network operation. With `auth:
+/// "app"` and App credentials configured, mints (or reuses a cached) installation token;
+/// otherwise falls back to the static `token` field as a classic PAT or caller-supplied
+/// installation token.
+fn resolve_credentials(
+    service: &ServiceConfig,
+    app_auth: &mut Option<AppAuth>,
+) -> Result<(Option<String>, AuthMode), Box<dyn Error>> {
+    if !service.uses_app_auth() {
+        return Ok((service.token.clone(), AuthMode::Pat));
+    }
+
+    match app_auth {
+        Some(auth) => Ok((Some(auth.token()?), AuthMode::App)),
+        None => Ok((service.token.clone(), AuthMode::App)),
+    }
+}
+
+/// Sleeps for `duration` in small increments, returning early once `running` is cleared,
+/// so a long backoff never delays shutdown.
+fn interruptible_sleep(duration: Duration, running: &AtomicBool) {
+    let step = Duration::from_millis(200);
+    let mut remaining = duration;
+    while remaining > Duration::ZERO && running.load(Ordering::SeqCst) {
+        let chunk = step.min(remaining);
+        thread::sleep(chunk);
+        remaining -= chunk;
+    }
+}
+
+fn log_db_err(result: rusqlite::Result<()>) {
+    if let Err(err) = result {
+        eprintln!("{} Failed to record history: {}", "FAILURE:".red().bold(), err);
+    }
+}
+
 fn short_sha(sha: &str) -> String {
     if sha.len() >= 8 {
         sha[..8].to_string()
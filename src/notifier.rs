@@ -0,0 +1,72 @@
+use colored::*;
+use serde::Deserialize;
+use std::process::Command;
+
+use crate::redact::redact;
+
+/// A configured notification sink. `mittorch.json` can list any number of these under
+/// `"notifiers"`; every sink receives every lifecycle event.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum NotifierSink {
+    /// Posts a JSON payload to a generic webhook URL (Slack, Discord, a pager, ...).
+    Webhook { url: String },
+    /// Runs a user script with the event fields passed in as environment variables.
+    Command { command: String },
+}
+
+/// Fires outbound notifications on orchestrator lifecycle events: process started, crash
+/// detected, update applied, and reload failure.
+pub struct Notifier {
+    sinks: Vec<NotifierSink>,
+}
+
+impl Notifier {
+    pub fn new(sinks: Vec<NotifierSink>) -> Self {
+        Self { sinks }
+    }
+
+    /// Sends `event` with `fields` to every configured sink. Per-sink failures are logged
+    /// and otherwise ignored — a broken notifier must never interrupt supervision.
+    pub fn notify(&self, event: &str, fields: &[(&str, &str)]) {
+        for sink in &self.sinks {
+            match sink {
+                NotifierSink::Webhook { url } => notify_webhook(url, event, fields),
+                NotifierSink::Command { command } => notify_command(command, event, fields),
+            }
+        }
+    }
+}
+
+fn notify_webhook(url: &str, event: &str, fields: &[(&str, &str)]) {
+    let mut payload = serde_json::Map::new();
+    payload.insert("event".to_string(), serde_json::Value::String(event.to_string()));
+    for (key, value) in fields {
+        payload.insert((*key).to_string(), serde_json::Value::String((*value).to_string()));
+    }
+
+    let client = reqwest::blocking::Client::new();
+    if let Err(err) = client.post(url).json(&payload).send() {
+        // Incoming-webhook URLs (Slack, Discord, ...) embed a bearer-token-like secret in
+        // the path, and `reqwest::Error`'s Display includes the request URL — redact it
+        // the same way `token` is redacted elsewhere.
+        eprintln!(
+            "{} Notifier webhook failed: {}",
+            "FAILURE:".red().bold(),
+            redact(&err.to_string(), &[url])
+        );
+    }
+}
+
+fn notify_command(command: &str, event: &str, fields: &[(&str, &str)]) {
+    let mut cmd = Command::new("bash");
+    cmd.arg("-c").arg(command);
+    cmd.env("MITTORCH_EVENT", event);
+    for (key, value) in fields {
+        cmd.env(format!("MITTORCH_{}", key.to_uppercase()), value);
+    }
+
+    if let Err(err) = cmd.status() {
+        eprintln!("{} Notifier command failed: {}", "FAILURE:".red().bold(), err);
+    }
+}
@@ -0,0 +1,12 @@
+/// Replaces every occurrence of a known secret with `***` so credentials never reach
+/// stdout, stderr, or any redirected log file. Empty secrets are ignored: replacing an
+/// empty pattern would insert `***` between every character of `text`.
+pub fn redact(text: &str, secrets: &[&str]) -> String {
+    let mut result = text.to_string();
+    for secret in secrets {
+        if !secret.is_empty() {
+            result = result.replace(*secret, "***");
+        }
+    }
+    result
+}
@@ -0,0 +1,181 @@
+use colored::*;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Largest push payload we'll buffer. GitHub's webhook payloads top out well under this;
+/// anything bigger is either misconfigured or hostile and gets rejected before we allocate.
+const MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+/// How long a single connection may sit idle before we give up on it. Keeps one slow or
+/// idle client from stalling the accept loop — and every push delivery behind it — forever.
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A push event extracted from a verified GitHub webhook payload.
+pub struct PushEvent {
+    pub full_name: String,
+    pub ref_name: String,
+    pub after: String,
+}
+
+/// Starts a small HTTP server that listens for GitHub `push` webhooks, verifies the
+/// `X-Hub-Signature-256` header against `secret`, and forwards decoded push events over
+/// `tx`. Runs until `running` is cleared.
+pub fn listen(
+    addr: &str,
+    port: u16,
+    secret: String,
+    running: Arc<AtomicBool>,
+) -> std::io::Result<mpsc::Receiver<PushEvent>> {
+    let (tx, rx) = mpsc::channel();
+    let listener = TcpListener::bind((addr, port))?;
+    listener.set_nonblocking(true)?;
+
+    println!(
+        "{} Webhook listener bound to {}:{}",
+        "SUCCESS:".green().bold(),
+        addr,
+        port
+    );
+
+    thread::spawn(move || {
+        while running.load(Ordering::SeqCst) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    if let Err(err) = handle_connection(stream, &secret, &tx) {
+                        eprintln!("{} Webhook request failed: {}", "FAILURE:".red().bold(), err);
+                    }
+                }
+                Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    thread::sleep(std::time::Duration::from_millis(200));
+                }
+                Err(err) => {
+                    eprintln!("{} Webhook listener error: {}", "FAILURE:".red().bold(), err);
+                }
+            }
+        }
+    });
+
+    Ok(rx)
+}
+
+fn handle_connection(
+    mut stream: TcpStream,
+    secret: &str,
+    tx: &mpsc::Sender<PushEvent>,
+) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(CONNECTION_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    let mut content_length: usize = 0;
+    let mut signature: Option<String> = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line == "\r\n" {
+            break;
+        }
+        let line = line.trim_end();
+        if let Some(value) = line.strip_prefix("Content-Length:").or_else(|| line.strip_prefix("content-length:")) {
+            content_length = value.trim().parse().unwrap_or(0);
+        } else if let Some(value) = line
+            .strip_prefix("X-Hub-Signature-256:")
+            .or_else(|| line.strip_prefix("x-hub-signature-256:"))
+        {
+            signature = Some(value.trim().to_string());
+        }
+    }
+
+    if content_length > MAX_BODY_BYTES {
+        respond(&mut stream, 413, "payload too large")?;
+        return Ok(());
+    }
+
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body)?;
+
+    let verified = match &signature {
+        Some(sig) => verify_signature(secret.as_bytes(), &body, sig),
+        None => false,
+    };
+
+    if !verified {
+        respond(&mut stream, 401, "unauthorized")?;
+        return Ok(());
+    }
+
+    match parse_push_event(&body) {
+        Some(event) => {
+            let _ = tx.send(event);
+            respond(&mut stream, 200, "ok")
+        }
+        None => respond(&mut stream, 400, "bad request"),
+    }
+}
+
+fn respond(stream: &mut TcpStream, code: u16, body: &str) -> std::io::Result<()> {
+    let reason = match code {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        413 => "Payload Too Large",
+        _ => "Error",
+    };
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        code,
+        reason,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes())
+}
+
+/// Computes `HMAC-SHA256` over `body` with `secret`, hex-encodes it, prefixes it with
+/// `sha256=`, and compares against `header` using a constant-time equality check.
+fn verify_signature(secret: &[u8], body: &[u8], header: &str) -> bool {
+    let mut mac = match HmacSha256::new_from_slice(secret) {
+        Ok(mac) => mac,
+        Err(_) => return false,
+    };
+    mac.update(body);
+    let expected = format!("sha256={}", hex_encode(&mac.finalize().into_bytes()));
+    constant_time_eq(expected.as_bytes(), header.as_bytes())
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+fn parse_push_event(body: &[u8]) -> Option<PushEvent> {
+    let json: serde_json::Value = serde_json::from_slice(body).ok()?;
+    let after = json["after"].as_str()?.to_string();
+    let full_name = json["repository"]["full_name"].as_str()?.to_string();
+    let ref_name = json["ref"].as_str()?.to_string();
+    Some(PushEvent {
+        full_name,
+        ref_name,
+        after,
+    })
+}